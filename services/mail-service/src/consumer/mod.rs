@@ -0,0 +1,258 @@
+use anyhow::Context;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::message::{BorrowedMessage, Message};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+
+use crate::dlq::DeadLetterProducer;
+use crate::mail::MailClient;
+use crate::settings::Settings;
+use crate::template::TemplateRegistry;
+use crate::validation::EventValidator;
+
+/// Bounds on how hard the consumer retries a failed send before giving up
+/// and routing the event to the dead-letter topic.
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub backoff_multiplier: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(200),
+            backoff_multiplier: 2.0,
+        }
+    }
+}
+
+impl From<&Settings> for RetryConfig {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            max_retries: settings.retry_max_retries,
+            initial_backoff: Duration::from_millis(settings.retry_initial_backoff_ms),
+            backoff_multiplier: settings.retry_backoff_multiplier,
+        }
+    }
+}
+
+/// Messages the consumer actor reacts to alongside incoming Kafka records.
+pub enum Command {
+    /// Stop consuming and return from the actor loop.
+    ShutDown,
+    /// Periodic liveness tick; logged so an operator can confirm the loop is alive.
+    HeartBeat,
+}
+
+/// Handle used to drive a running consumer actor from the outside.
+#[derive(Clone)]
+pub struct ConsumerHandle {
+    commands: mpsc::Sender<Command>,
+}
+
+impl ConsumerHandle {
+    pub async fn shutdown(&self) -> anyhow::Result<()> {
+        self.commands
+            .send(Command::ShutDown)
+            .await
+            .context("consumer actor already stopped")
+    }
+}
+
+/// Everything the consumer actor needs besides where to connect, grouped so
+/// `spawn` takes one bundle instead of a long parameter list.
+pub struct ConsumerDeps {
+    pub mail_client: MailClient,
+    pub templates: TemplateRegistry,
+    pub validator: EventValidator,
+    pub dlq: DeadLetterProducer,
+    pub retry: RetryConfig,
+}
+
+/// Spawns the `mail-events` consumer actor and returns a handle plus its join future.
+///
+/// The actor subscribes to `topic` in `group`, decodes each payload as a
+/// `serde_json::Value`, and sends it through the [`MailClient`] (rendering
+/// the named template first when the event has one). A send that
+/// fails is retried with backoff; if it still fails (or the payload failed
+/// schema validation) the event is forwarded to the dead-letter topic.
+/// Offsets of decoded events are only committed once the event has been sent
+/// or dead-lettered, so a crash mid-retry redelivers the message instead of
+/// losing it. Empty payloads and payloads that are not valid JSON are logged
+/// and committed without being dead-lettered. Commit
+/// and dead-letter-publish failures are logged rather than propagated, so a
+/// transient broker hiccup on one message doesn't take down the whole actor
+/// and stall the partition. A background ticker sends `Command::HeartBeat`
+/// through the same channel as `ShutDown`, so both are handled by the one
+/// actor loop.
+pub fn spawn(
+    brokers: &str,
+    topic: &str,
+    group: &str,
+    deps: ConsumerDeps,
+) -> anyhow::Result<(ConsumerHandle, tokio::task::JoinHandle<anyhow::Result<()>>)> {
+    let ConsumerDeps { mail_client, templates, validator, dlq, retry } = deps;
+    let consumer: StreamConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", group)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest")
+        .create()
+        .context("failed to create mail-events consumer")?;
+    consumer
+        .subscribe(&[topic])
+        .with_context(|| format!("failed to subscribe to {topic}"))?;
+
+    let (tx, mut rx) = mpsc::channel(8);
+    let handle = ConsumerHandle { commands: tx.clone() };
+
+    let heartbeat_tx = tx;
+    tokio::spawn(async move {
+        let mut heartbeat = interval(Duration::from_secs(30));
+        loop {
+            heartbeat.tick().await;
+            if heartbeat_tx.send(Command::HeartBeat).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let join = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                cmd = rx.recv() => match cmd {
+                    Some(Command::ShutDown) | None => break,
+                    Some(Command::HeartBeat) => println!("mail-events consumer heartbeat"),
+                },
+                message = consumer.recv() => {
+                    let message = match message {
+                        Ok(message) => message,
+                        Err(err) => {
+                            eprintln!("mail-events consumer error: {err}");
+                            continue;
+                        }
+                    };
+                    let payload = match message.payload() {
+                        Some(payload) => payload,
+                        None => {
+                            commit(&consumer, &message);
+                            continue;
+                        }
+                    };
+                    let event: Value = match serde_json::from_slice(payload) {
+                        Ok(event) => event,
+                        Err(err) => {
+                            eprintln!("mail-events consumer: dropping undecodable payload: {err}");
+                            commit(&consumer, &message);
+                            continue;
+                        }
+                    };
+                    if let Err(failure) = validator.validate(&event) {
+                        eprintln!("mail-events consumer: rejecting invalid payload: {failure}");
+                        if dead_letter(&dlq, &event, "schema_validation", &failure.details).await {
+                            commit(&consumer, &message);
+                        }
+                        continue;
+                    }
+
+                    match send_with_retry(&mail_client, &templates, &event, &retry).await {
+                        Ok(()) => {
+                            commit(&consumer, &message);
+                        }
+                        Err(err) => {
+                            eprintln!("mail-events consumer: send_email failed after {} retries, sending to DLQ: {err}", retry.max_retries);
+                            if dead_letter(&dlq, &event, "send_failed", &err.to_string()).await {
+                                commit(&consumer, &message);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    });
+
+    Ok((handle, join))
+}
+
+/// Commits `message`'s offset, logging rather than failing the actor if the
+/// broker rejects it — a transient commit failure just means the message is
+/// redelivered and reprocessed, not that the whole consumer should die.
+fn commit(consumer: &StreamConsumer, message: &BorrowedMessage<'_>) {
+    if let Err(err) = consumer.commit_message(message, rdkafka::consumer::CommitMode::Async) {
+        eprintln!("mail-events consumer: failed to commit offset, message may be redelivered: {err}");
+    }
+}
+
+/// Publishes `event` to the dead-letter topic, logging (rather than
+/// propagating) a publish failure. Returns whether the publish succeeded, so
+/// the caller can leave the offset uncommitted on failure and let Kafka
+/// redeliver the message for another dead-letter attempt instead of losing it.
+async fn dead_letter(dlq: &DeadLetterProducer, event: &Value, reason: &str, details: &str) -> bool {
+    match dlq.publish(event, reason, details).await {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("mail-events consumer: failed to publish to DLQ, leaving offset uncommitted for redelivery: {err}");
+            false
+        }
+    }
+}
+
+/// Sends `event`, retrying with exponential backoff on failure up to
+/// `retry.max_retries` before giving up.
+async fn send_with_retry(
+    mail_client: &MailClient,
+    templates: &TemplateRegistry,
+    event: &Value,
+    retry: &RetryConfig,
+) -> anyhow::Result<()> {
+    let mut backoff = retry.initial_backoff;
+    let mut attempt = 0;
+    loop {
+        match handle_event(mail_client, templates, event).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < retry.max_retries => {
+                attempt += 1;
+                eprintln!("mail-events consumer: send attempt {attempt}/{} failed, retrying in {backoff:?}: {err}", retry.max_retries);
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.mul_f64(retry.backoff_multiplier);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+async fn handle_event(mail_client: &MailClient, templates: &TemplateRegistry, event: &Value) -> anyhow::Result<()> {
+    let to = event
+        .get("to")
+        .and_then(Value::as_str)
+        .context("mail-events payload missing `to`")?
+        .to_string();
+    let subject = event
+        .get("subject")
+        .and_then(Value::as_str)
+        .context("mail-events payload missing `subject`")?
+        .to_string();
+
+    if let Some(template_name) = event.get("template").and_then(Value::as_str) {
+        let vars = event.get("vars").cloned().unwrap_or_default();
+        let html = templates
+            .render_html(template_name, &vars)
+            .with_context(|| format!("rendering template `{template_name}`"))?;
+        let text = templates
+            .render_text(template_name, &vars)
+            .with_context(|| format!("rendering plaintext alternative for `{template_name}`"))?;
+        return mail_client.send_rendered(to, subject, html, text).await;
+    }
+
+    let body = event
+        .get("body")
+        .and_then(Value::as_str)
+        .context("mail-events payload missing `body` (or a `template`)")?
+        .to_string();
+    mail_client.send_email(to, subject, body).await
+}
@@ -0,0 +1,46 @@
+use anyhow::Context;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use serde_json::{json, Value};
+
+/// Producer used to forward undeliverable `mail-events` to the dead-letter
+/// topic. Shared across the consumer loop rather than rebuilt per message.
+pub struct DeadLetterProducer {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl DeadLetterProducer {
+    pub fn new(brokers: &str, topic: impl Into<String>) -> anyhow::Result<Self> {
+        let producer: FutureProducer = rdkafka::config::ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .create()
+            .context("failed to create mail-events dead-letter producer")?;
+        Ok(Self {
+            producer,
+            topic: topic.into(),
+        })
+    }
+
+    /// Publishes `event` plus failure metadata so operators get a replayable
+    /// record of mail that could not be delivered.
+    pub async fn publish(&self, event: &Value, reason: &str, details: &str) -> anyhow::Result<()> {
+        let envelope = json!({
+            "event": event,
+            "failure": {
+                "reason": reason,
+                "details": details,
+            },
+        });
+        let payload = serde_json::to_vec(&envelope).context("failed to encode dead-letter envelope")?;
+        self.producer
+            .send(
+                FutureRecord::<(), _>::to(&self.topic).payload(&payload),
+                Timeout::After(std::time::Duration::from_secs(5)),
+            )
+            .await
+            .map_err(|(err, _)| err)
+            .context("failed to publish to mail-events dead-letter topic")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Context;
+use lettre::{
+    message::header::ContentType,
+    message::Mailbox,
+    message::{MultiPart, SinglePart},
+    transport::file::AsyncFileTransport,
+    transport::smtp::authentication::Credentials,
+    transport::smtp::PoolConfig,
+    AsyncSmtpTransport,
+    AsyncTransport,
+    Message,
+    Tokio1Executor,
+};
+
+use crate::settings::Settings;
+
+/// Tunable knobs for the pooled SMTP connection.
+pub struct MailClientConfig {
+    pub max_connections: u32,
+    pub idle_timeout: Duration,
+}
+
+impl Default for MailClientConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            idle_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl From<&Settings> for MailClientConfig {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            max_connections: settings.smtp_pool_max_connections,
+            idle_timeout: Duration::from_secs(settings.smtp_pool_idle_timeout_secs),
+        }
+    }
+}
+
+/// An email captured by [`MailTransport::Stub`] instead of being sent anywhere.
+/// Fields are only read via [`MailClient::captured`], which nothing but tests
+/// call today — allowed rather than deleted since it's the documented way to
+/// assert on stub-mode sends.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CapturedEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Where outgoing mail actually goes. `File` uses lettre's
+/// `file-transport`/`file-transport-envelope` and `Stub` keeps mail in
+/// memory, so local dev and tests never have to hit Zoho.
+enum MailTransport {
+    /// Pooled SMTP relay, used in staging/prod.
+    Smtp(Arc<AsyncSmtpTransport<Tokio1Executor>>),
+    /// Writes each message to `dir` as a `.eml` plus a sidecar JSON envelope.
+    File(AsyncFileTransport<Tokio1Executor>),
+    /// Keeps sent messages in memory so tests can assert on them.
+    Stub(Arc<Mutex<Vec<CapturedEmail>>>),
+}
+
+/// Mail client built once at startup and shared into every consumer task.
+/// Dispatches through whichever [`MailTransport`] was selected so repeated
+/// sends reuse warm connections instead of re-handshaking per email.
+#[derive(Clone)]
+pub struct MailClient {
+    from: Mailbox,
+    transport: Arc<MailTransport>,
+}
+
+impl MailClient {
+    /// Pooled SMTP transport talking to `settings.smtp_host`/`smtp_port`.
+    pub fn smtp(settings: &Settings, config: MailClientConfig) -> anyhow::Result<Self> {
+        let smtp_host = settings
+            .smtp_host
+            .as_deref()
+            .context("smtp_host is required when MAIL_TRANSPORT=smtp (or unset)")?;
+        let smtp_user = settings
+            .smtp_user
+            .clone()
+            .context("smtp_user is required when MAIL_TRANSPORT=smtp (or unset)")?;
+        let smtp_pass = settings
+            .smtp_pass
+            .clone()
+            .context("smtp_pass is required when MAIL_TRANSPORT=smtp (or unset)")?;
+        let creds = Credentials::new(smtp_user, smtp_pass);
+        let pool_config = PoolConfig::new()
+            .max_size(config.max_connections)
+            .idle_timeout(config.idle_timeout);
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)?
+            .port(settings.smtp_port)
+            .credentials(creds)
+            .pool_config(pool_config)
+            .build();
+        Ok(Self {
+            from: settings.email_sender.parse()?,
+            transport: Arc::new(MailTransport::Smtp(Arc::new(transport))),
+        })
+    }
+
+    /// Writes each message under `dir` instead of sending it, for local dev.
+    pub fn file(settings: &Settings, dir: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        Ok(Self {
+            from: settings.email_sender.parse()?,
+            transport: Arc::new(MailTransport::File(AsyncFileTransport::with_envelope(dir.into()))),
+        })
+    }
+
+    /// Captures messages in memory instead of sending them, for tests.
+    pub fn stub(settings: &Settings) -> anyhow::Result<Self> {
+        Ok(Self {
+            from: settings.email_sender.parse()?,
+            transport: Arc::new(MailTransport::Stub(Arc::new(Mutex::new(Vec::new())))),
+        })
+    }
+
+    /// The messages captured so far. Only meaningful for [`MailClient::stub`].
+    #[allow(dead_code)]
+    pub fn captured(&self) -> Vec<CapturedEmail> {
+        match self.transport.as_ref() {
+            MailTransport::Stub(sink) => sink.lock().unwrap().clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Sends a fully-rendered HTML body with no plaintext alternative.
+    pub async fn send_email(&self, email: String, subject: String, body: String) -> anyhow::Result<()> {
+        self.send_rendered(email, subject, body, None).await
+    }
+
+    /// Sends a rendered email, attaching `text` as a plaintext alternative
+    /// part alongside the HTML body when one is supplied.
+    pub async fn send_rendered(
+        &self,
+        email: String,
+        subject: String,
+        html: String,
+        text: Option<String>,
+    ) -> anyhow::Result<()> {
+        let builder = Message::builder()
+            .from(self.from.clone())
+            .to(Mailbox::new(None, email.parse()?))
+            .subject(subject.clone());
+        let message = match text {
+            Some(text) => builder.multipart(
+                MultiPart::alternative()
+                    .singlepart(SinglePart::plain(text))
+                    .singlepart(SinglePart::html(html.clone())),
+            )?,
+            None => builder
+                .header(ContentType::TEXT_HTML)
+                .body(html.clone())
+                .unwrap(),
+        };
+
+        match self.transport.as_ref() {
+            MailTransport::Smtp(transport) => {
+                transport.send(message).await.context("ZOHO failed !")?;
+            }
+            MailTransport::File(transport) => {
+                transport.send(message).await.context("failed to write email to file transport")?;
+            }
+            MailTransport::Stub(sink) => {
+                sink.lock().unwrap().push(CapturedEmail { to: email, subject, body: html });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_settings() -> Settings {
+        Settings {
+            email_sender: "noreply@example.com".to_string(),
+            smtp_host: Some("smtp.example.com".to_string()),
+            smtp_port: 587,
+            smtp_user: Some("user".to_string()),
+            smtp_pass: Some("pass".to_string()),
+            smtp_pool_max_connections: 10,
+            smtp_pool_idle_timeout_secs: 60,
+            retry_max_retries: 5,
+            retry_initial_backoff_ms: 200,
+            retry_backoff_multiplier: 2.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn stub_captures_sent_emails() {
+        let client = MailClient::stub(&test_settings()).unwrap();
+        client
+            .send_email("to@example.com".to_string(), "hi".to_string(), "body".to_string())
+            .await
+            .unwrap();
+
+        let captured = client.captured();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].to, "to@example.com");
+        assert_eq!(captured[0].subject, "hi");
+        assert_eq!(captured[0].body, "body");
+    }
+
+    #[tokio::test]
+    async fn stub_starts_with_nothing_captured() {
+        let client = MailClient::stub(&test_settings()).unwrap();
+        assert!(client.captured().is_empty());
+    }
+
+    #[test]
+    fn stub_and_file_work_without_smtp_credentials() {
+        let mut settings = test_settings();
+        settings.smtp_host = None;
+        settings.smtp_user = None;
+        settings.smtp_pass = None;
+
+        assert!(MailClient::stub(&settings).is_ok());
+        assert!(MailClient::file(&settings, std::env::temp_dir()).is_ok());
+    }
+
+    #[test]
+    fn smtp_requires_smtp_host() {
+        let mut settings = test_settings();
+        settings.smtp_host = None;
+
+        match MailClient::smtp(&settings, MailClientConfig::default()) {
+            Ok(_) => panic!("expected an error when smtp_host is missing"),
+            Err(err) => assert!(err.to_string().contains("smtp_host")),
+        }
+    }
+
+    #[tokio::test]
+    async fn file_transport_writes_eml_and_envelope() {
+        let dir = std::env::temp_dir().join(format!("mail-service-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let client = MailClient::file(&test_settings(), &dir).unwrap();
+        client
+            .send_email("to@example.com".to_string(), "hi".to_string(), "body".to_string())
+            .await
+            .unwrap();
+
+        let entries: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        let has_eml = entries.iter().any(|path| path.extension().is_some_and(|ext| ext == "eml"));
+        let has_json = entries.iter().any(|path| path.extension().is_some_and(|ext| ext == "json"));
+        assert!(has_eml, "expected a .eml file in {dir:?}, found {entries:?}");
+        assert!(has_json, "expected a .json envelope in {dir:?}, found {entries:?}");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,21 +1,54 @@
+mod consumer;
+mod dlq;
 mod mail;
+mod settings;
+mod template;
+mod validation;
 
-use serde_json::json;
+use consumer::{ConsumerDeps, RetryConfig};
+use dlq::DeadLetterProducer;
 use dotenvy::dotenv;
-
+use mail::{MailClient, MailClientConfig};
+use settings::Settings;
+use template::TemplateRegistry;
+use validation::EventValidator;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let brokers = "localhost:9092";
+    dotenv().ok();
+
+    let settings = Settings::load()?;
+    let brokers = std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".to_string());
     let topic = "mail-events";
     let group = "mail-group";
-    let brokers_clone = brokers.to_string();
-    let topic_clone = topic.to_string();
-    dotenv().ok();
-    let producer_task = tokio::spawn(async move {
-        let event = json!({"to": "user@matchapp.com", "subject": "New Match"});
-        println!("thread")
+
+    let mail_client = match std::env::var("MAIL_TRANSPORT").as_deref() {
+        Ok("file") => MailClient::file(
+            &settings,
+            std::env::var("MAIL_FILE_DIR").unwrap_or_else(|_| "./tmp/mail".to_string()),
+        )?,
+        Ok("stub") => MailClient::stub(&settings)?,
+        _ => MailClient::smtp(&settings, MailClientConfig::from(&settings))?,
+    };
+    let templates = TemplateRegistry::load()?;
+    let validator = EventValidator::compile()?;
+    let dlq = DeadLetterProducer::new(&brokers, "mail-events.dlq")?;
+    let retry = RetryConfig::from(&settings);
+
+    let (handle, join) = consumer::spawn(
+        &brokers,
+        topic,
+        group,
+        ConsumerDeps { mail_client, templates, validator, dlq, retry },
+    )?;
+
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            let _ = shutdown_handle.shutdown().await;
+        }
     });
-    tokio::try_join!(producer_task)?;
+
+    join.await??;
     Ok(())
 }
@@ -0,0 +1,101 @@
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Mail service configuration, loaded from an optional `config.toml`
+/// overlaid with environment variables (env wins). Validated once at
+/// startup so a missing or malformed value fails fast with a clear message
+/// instead of an `unwrap`/`?` deep inside `send_email`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Settings {
+    pub email_sender: String,
+    /// Required only when `MAIL_TRANSPORT` selects the SMTP transport (the
+    /// default) — file/stub mode never connects to a relay.
+    #[serde(default)]
+    pub smtp_host: Option<String>,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub smtp_user: Option<String>,
+    #[serde(default)]
+    pub smtp_pass: Option<String>,
+    /// Max warm connections kept open in the SMTP pool.
+    #[serde(default = "default_smtp_pool_max_connections")]
+    pub smtp_pool_max_connections: u32,
+    /// How long an idle pooled SMTP connection is kept before being closed.
+    #[serde(default = "default_smtp_pool_idle_timeout_secs")]
+    pub smtp_pool_idle_timeout_secs: u64,
+    /// How many times a failed send is retried before it's dead-lettered.
+    #[serde(default = "default_retry_max_retries")]
+    pub retry_max_retries: u32,
+    /// Delay before the first retry, in milliseconds.
+    #[serde(default = "default_retry_initial_backoff_ms")]
+    pub retry_initial_backoff_ms: u64,
+    /// Factor the backoff delay is multiplied by after each retry.
+    #[serde(default = "default_retry_backoff_multiplier")]
+    pub retry_backoff_multiplier: f64,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_pool_max_connections() -> u32 {
+    10
+}
+
+fn default_smtp_pool_idle_timeout_secs() -> u64 {
+    60
+}
+
+fn default_retry_max_retries() -> u32 {
+    5
+}
+
+fn default_retry_initial_backoff_ms() -> u64 {
+    200
+}
+
+fn default_retry_backoff_multiplier() -> f64 {
+    2.0
+}
+
+impl Settings {
+    /// Loads and validates settings. `config.toml`, if present, supplies
+    /// defaults; environment variables (`EMAIL_SENDER`, `SMTP_HOST`,
+    /// `SMTP_PORT`, `SMTP_USER`, `SMTP_PASS`) always take precedence.
+    /// `smtp_host`/`smtp_user`/`smtp_pass` are only required when
+    /// `MAIL_TRANSPORT` actually selects SMTP; see [`MailClient::smtp`].
+    ///
+    /// [`MailClient::smtp`]: crate::mail::MailClient::smtp
+    pub fn load() -> anyhow::Result<Self> {
+        let mut builder = config::Config::builder();
+        if std::path::Path::new("config.toml").exists() {
+            builder = builder.add_source(config::File::with_name("config"));
+        }
+        builder = builder.add_source(config::Environment::default().try_parsing(true));
+
+        let settings: Settings = builder
+            .build()
+            .context("failed to load mail-service settings")?
+            .try_deserialize()
+            .context("invalid mail-service settings: expected at least email_sender")?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        self.email_sender
+            .parse::<lettre::Address>()
+            .with_context(|| format!("invalid email_sender `{}`", self.email_sender))?;
+        anyhow::ensure!(self.smtp_port != 0, "smtp_port must not be zero");
+        anyhow::ensure!(
+            self.smtp_pool_max_connections != 0,
+            "smtp_pool_max_connections must not be zero"
+        );
+        anyhow::ensure!(
+            self.retry_backoff_multiplier >= 1.0,
+            "retry_backoff_multiplier must be at least 1.0"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+use tera::Tera;
+
+/// A named, reusable email body. `mail-events` messages can reference one of
+/// these by name instead of shipping a fully-rendered body.
+///
+/// Bundled templates live under `template/templates/` as `<name>.html.tera`
+/// with an optional `<name>.txt.tera` plaintext alternative.
+pub struct TemplateRegistry {
+    tera: Tera,
+}
+
+impl TemplateRegistry {
+    /// Loads and compiles the bundled templates once at startup.
+    pub fn load() -> anyhow::Result<Self> {
+        let mut tera = Tera::default();
+        tera.add_raw_templates(vec![
+            ("new_match.html", include_str!("templates/new_match.html.tera")),
+            ("new_match.txt", include_str!("templates/new_match.txt.tera")),
+            ("verification.html", include_str!("templates/verification.html.tera")),
+        ])
+        .context("failed to compile bundled mail templates")?;
+        Ok(Self { tera })
+    }
+
+    /// Renders the HTML body for `name` with `vars`, failing loudly if the
+    /// template or a variable it requires is missing.
+    pub fn render_html(&self, name: &str, vars: &Value) -> anyhow::Result<String> {
+        self.render(&format!("{name}.html"), vars)
+    }
+
+    /// Renders the plaintext alternative for `name`, if one is bundled.
+    pub fn render_text(&self, name: &str, vars: &Value) -> anyhow::Result<Option<String>> {
+        let text_name = format!("{name}.txt");
+        if self.tera.get_template_names().any(|registered| registered == text_name) {
+            Ok(Some(self.render(&text_name, vars)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn render(&self, name: &str, vars: &Value) -> anyhow::Result<String> {
+        let context = tera::Context::from_serialize(vars)
+            .with_context(|| format!("template `{name}` variables must be a JSON object"))?;
+        self.tera
+            .render(name, &context)
+            .map_err(|err| anyhow!("failed to render template `{name}`: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_html_with_vars() {
+        let templates = TemplateRegistry::load().unwrap();
+        let vars = json!({ "name": "Alex", "match_name": "Sam", "profile_url": "https://example.com/sam" });
+        let html = templates.render_html("new_match", &vars).unwrap();
+        assert!(html.contains("Hi Alex,"));
+        assert!(html.contains("Sam"));
+        // `.html` templates are auto-escaped by Tera, so `/` becomes `&#x2F;`.
+        assert!(html.contains("example.com"));
+    }
+
+    #[test]
+    fn renders_text_alternative_when_bundled() {
+        let templates = TemplateRegistry::load().unwrap();
+        let vars = json!({ "name": "Alex", "match_name": "Sam", "profile_url": "https://example.com/sam" });
+        let text = templates.render_text("new_match", &vars).unwrap();
+        assert!(text.unwrap().contains("Hi Alex,"));
+    }
+
+    #[test]
+    fn text_alternative_is_none_when_not_bundled() {
+        let templates = TemplateRegistry::load().unwrap();
+        let vars = json!({ "name": "Alex", "verification_url": "https://example.com/verify" });
+        let text = templates.render_text("verification", &vars).unwrap();
+        assert!(text.is_none());
+    }
+
+    #[test]
+    fn unknown_template_name_errors() {
+        let templates = TemplateRegistry::load().unwrap();
+        let result = templates.render_html("does_not_exist", &json!({}));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn missing_required_variable_errors() {
+        let templates = TemplateRegistry::load().unwrap();
+        let result = templates.render_html("new_match", &json!({ "name": "Alex" }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// A `mail-events` payload failed schema validation. Carries the joined
+/// validator error messages so callers can log or DLQ the event without
+/// re-deriving what was wrong with it.
+#[derive(Debug, Error)]
+#[error("mail-events payload failed schema validation: {details}")]
+pub struct ValidationFailure {
+    pub details: String,
+}
+
+/// Validates decoded `mail-events` payloads against a compiled JSON Schema
+/// before any transport work happens, so a malformed event is rejected with
+/// a structured error instead of panicking deep in `send_email` or producing
+/// garbage mail.
+pub struct EventValidator {
+    schema: JSONSchema,
+}
+
+impl EventValidator {
+    /// Compiles the event schema once; call this at startup.
+    pub fn compile() -> anyhow::Result<Self> {
+        let schema_doc = json!({
+            "type": "object",
+            "required": ["to", "subject"],
+            "properties": {
+                "to": { "type": "string", "format": "email" },
+                "subject": { "type": "string", "minLength": 1, "maxLength": 998 },
+                "body": { "type": "string", "maxLength": 200_000 },
+                "template": { "type": "string", "minLength": 1 },
+                "vars": { "type": "object" }
+            },
+            "oneOf": [
+                { "required": ["body"] },
+                { "required": ["template"] }
+            ]
+        });
+        let schema = JSONSchema::compile(&schema_doc)
+            .map_err(|err| anyhow::anyhow!("invalid mail-events schema: {err}"))?;
+        Ok(Self { schema })
+    }
+
+    /// Checks `event` against the compiled schema.
+    pub fn validate(&self, event: &Value) -> Result<(), ValidationFailure> {
+        self.schema.validate(event).map_err(|errors| {
+            let details = errors.map(|err| err.to_string()).collect::<Vec<_>>().join("; ");
+            ValidationFailure { details }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_event_with_body() {
+        let validator = EventValidator::compile().unwrap();
+        let event = json!({ "to": "a@example.com", "subject": "hi", "body": "hello" });
+        assert!(validator.validate(&event).is_ok());
+    }
+
+    #[test]
+    fn accepts_event_with_template() {
+        let validator = EventValidator::compile().unwrap();
+        let event = json!({ "to": "a@example.com", "subject": "hi", "template": "new_match" });
+        assert!(validator.validate(&event).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_to() {
+        let validator = EventValidator::compile().unwrap();
+        let event = json!({ "subject": "hi", "body": "hello" });
+        assert!(validator.validate(&event).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_email() {
+        let validator = EventValidator::compile().unwrap();
+        let event = json!({ "to": "not-an-email", "subject": "hi", "body": "hello" });
+        assert!(validator.validate(&event).is_err());
+    }
+
+    #[test]
+    fn rejects_missing_body_and_template() {
+        let validator = EventValidator::compile().unwrap();
+        let event = json!({ "to": "a@example.com", "subject": "hi" });
+        assert!(validator.validate(&event).is_err());
+    }
+}